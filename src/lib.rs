@@ -23,7 +23,9 @@
 //!
 //! In release builds, `safe_unwrap!(expr)` is equivalent to `expr.unwrap()`;
 //! in debug builds, `expect()` will be called with a message indicating that
-//! the assumed invariant has been violated.
+//! the assumed invariant has been violated, together with the `file!()`,
+//! `line!()` and `column!()` of the offending `safe_unwrap!` call, so a
+//! violation can be traced back to its source even in a large codebase.
 //!
 //! Alternative, for `Result` and `Option` types, you can risk a small bit of
 //! overhead in exchange for nicer syntax:
@@ -40,11 +42,13 @@
 //!
 //!     assert_eq!(val, 42);
 //!
+//!     // `unwrap_or_abort` works even without `std`.
+//!     let val = res.unwrap_or_abort("is constant value");
+//!     assert_eq!(val, 42);
+//!
 //!     #[cfg(feature = "std")]
 //!     {
-//!         // With `std`, two additional methods are available.
-//!         let val = res.unwrap_or_abort("is constant value");
-//!         assert_eq!(val, 42);
+//!         // With `std`, `unwrap_or_exit` is also available.
 //!         let val = res.unwrap_or_exit("is constant value");
 //!         assert_eq!(val, 42);
 //!     }
@@ -57,14 +61,109 @@
 //! from the resulting executable (often works in release mode).
 //!
 //!
+//! ## Documenting why an unwrap is safe
+//!
+//! `safe_unwrap` conflates several distinct reasons an unwrap might be
+//! believed safe. `SafeUnwrap` also offers `verified`, `assured` and `todo`,
+//! which behave like `safe_unwrap` at runtime (except as noted for `todo`)
+//! but let a reviewer see *why* at a glance:
+//!
+//! * `verified(msg)` - an explicit conditional earlier in the function
+//!   guarantees the value is present.
+//! * `assured(msg)` - some invariant external to this function guarantees it.
+//! * `todo(msg)` - the `None`/`Err` path just hasn't been handled yet; unlike
+//!   the other methods, on `None`/`Err` it panics with `msg` (and reports to
+//!   the violation hook) in release builds too, rather than doing a bare
+//!   `unwrap()`, since it marks an unfinished path rather than a proven
+//!   invariant.
+//!
+//!
+//! ## Destructuring arbitrary enums
+//!
+//! `SafeUnwrap` and the plain form of `safe_unwrap!` only know about
+//! `Option` and `Result`. When you have already proven you are in a specific
+//! variant of some other enum (a parser AST node, a state machine, ...) and
+//! just want to pull the fields back out, use the pattern form instead:
+//!
+//! ```
+//! #[macro_use]
+//! extern crate safe_unwrap;
+//!
+//! enum PatternElement {
+//!     Tag { key_subtree: usize },
+//!     Text(String),
+//! }
+//!
+//! fn main() {
+//!     let elem = PatternElement::Tag { key_subtree: 42 };
+//!
+//!     // we already know `elem` is a `Tag`, so pull `key_subtree` back out
+//!     let (key_subtree,) = safe_unwrap!("elem is always a Tag here", PatternElement::Tag { key_subtree } = elem);
+//!
+//!     assert_eq!(key_subtree, 42);
+//! }
+//! ```
+//!
+//! In debug builds this expands to a `match` that binds the requested fields
+//! and `unreachable!`s with the usual `"[BUG] violated: <reason>"` message on
+//! any other arm. In release builds the mismatch arm becomes an
+//! `unreachable_unchecked()`, so there is no runtime cost (and no requirement
+//! that the enum's other variants implement `Debug`) once the invariant is
+//! trusted.
+//!
+//!
 //! ## `std` support
 //!
 //! By default, `no_std` is supported. With the `std` feature, `SafeUnwrap` has
-//! two additional methods, which require the standard library. They work the
-//! same way as `safe_unwrap`, but:
+//! an additional method, which requires the standard library:
 //!
-//! * `unwrap_or_abort` aborts the process instead of panicking.
 //! * `unwrap_or_exit` exits with code 1 instead of panicking.
+//!
+//! `unwrap_or_abort` (and the matching `safe_unwrap_abort!` macro) terminate
+//! the process without unwinding instead, which is sound to call from `unsafe`
+//! code or an FFI boundary even while another panic is already unwinding.
+//! Unlike `unwrap_or_exit`, it does not require `std`: without it, there is no
+//! process to abort, so on x86_64 and aarch64 it traps with an illegal
+//! instruction instead, and on other targets it spins forever.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate safe_unwrap;
+//!
+//! fn main() {
+//!     let res: Option<usize> = Some(42);
+//!     let val = safe_unwrap_abort!("is constant value", res);
+//!     assert_eq!(val, 42);
+//! }
+//! ```
+//!
+//!
+//! ## Routing violations elsewhere
+//!
+//! By default, a violation that aborts or exits is reported on `stderr` (when
+//! the `std` feature is enabled; a panicking violation just goes through
+//! Rust's own panic hook, as usual). Call [`set_violation_hook`] to replace
+//! that with your own handler, e.g. to forward violations to `log`/`tracing`,
+//! a serial port, or a crash reporter:
+//!
+//! ```
+//! use safe_unwrap::{set_violation_hook, ViolationInfo};
+//!
+//! fn my_hook(info: &ViolationInfo) {
+//!     eprintln!("{:?} at {}:{}: {}",
+//!         info.action, info.location.file, info.location.line, info.reason);
+//! }
+//!
+//! set_violation_hook(my_hook);
+//! ```
+//!
+//! There is only one global hook. It is called just before the terminating
+//! action is taken from every `safe_unwrap_abort!` expansion and every
+//! `todo`, `unwrap_or_abort` and `unwrap_or_exit` call, in all builds. The
+//! panicking paths of `safe_unwrap!`, `safe_unwrap`, `verified` and `assured`
+//! call it in debug builds only; in release builds they are a bare `unwrap()`
+//! (or `unreachable_unchecked()` for the pattern form of `safe_unwrap!`) and
+//! do not call the hook.
 
 #[cfg(feature = "std")]
 extern crate std;
@@ -72,92 +171,481 @@ extern crate std;
 #[cfg(feature = "std")]
 use std::io::Write;
 
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Where in the source a violated invariant was found.
+#[derive(Debug, Clone, Copy)]
+pub struct Location {
+    pub file: &'static str,
+    pub line: u32,
+    pub column: u32,
+}
+
+/// What `safe_unwrap` is about to do in response to a violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViolationAction {
+    /// The current thread is about to panic.
+    Panic,
+    /// The process is about to abort, without unwinding.
+    Abort,
+    /// The process is about to exit with a non-zero status.
+    Exit,
+}
+
+/// Everything a violation hook registered with [`set_violation_hook`] is told
+/// about a violated invariant, just before the terminating action is taken.
+#[derive(Debug, Clone, Copy)]
+pub struct ViolationInfo {
+    pub reason: &'static str,
+    pub location: Location,
+    pub action: ViolationAction,
+}
+
+static VIOLATION_HOOK: AtomicUsize = AtomicUsize::new(0);
+
+/// Register a global hook that is invoked with a [`ViolationInfo`] just
+/// before `safe_unwrap` panics, aborts or exits, so applications can forward
+/// violations to `log`/`tracing`, a serial port, or a crash reporter instead
+/// of `stderr`.
+///
+/// There is only one global hook; calling this again replaces the previous
+/// one. When no hook has been registered, violations that abort or exit fall
+/// back to being reported on `stderr` (with the `std` feature); panics
+/// continue to go through Rust's own panic hook either way.
+///
+/// In release builds, the panicking paths of `safe_unwrap!`, `safe_unwrap`,
+/// `verified` and `assured` do not call the hook.
+pub fn set_violation_hook(hook: fn(&ViolationInfo)) {
+    VIOLATION_HOOK.store(hook as usize, Ordering::SeqCst);
+}
+
+#[doc(hidden)]
+pub fn report_violation(info: &ViolationInfo) {
+    let ptr = VIOLATION_HOOK.load(Ordering::SeqCst);
+    if ptr != 0 {
+        let hook: fn(&ViolationInfo) = unsafe { core::mem::transmute(ptr) };
+        hook(info);
+    } else if info.action != ViolationAction::Panic {
+        #[cfg(feature = "std")]
+        {
+            let _ = writeln!(std::io::stderr(), "{}", info.reason);
+        }
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+#[inline]
+pub fn caller_location() -> Location {
+    let loc = core::panic::Location::caller();
+    Location {
+        file: loc.file(),
+        line: loc.line(),
+        column: loc.column(),
+    }
+}
+
+/// Panics with `Result::expect`'s message (`"{msg}: {e:?}"`), after the
+/// `Err` has already been matched on elsewhere.
+#[track_caller]
+#[inline]
+fn expect_err<T, E: core::fmt::Debug>(e: E, msg: &'static str) -> T {
+    panic!("{}: {:?}", msg, e)
+}
+
+/// Panics with `Option::expect`'s message, after the `None` has already been
+/// matched on elsewhere.
+#[track_caller]
+#[inline]
+fn expect_none<T>(msg: &'static str) -> T {
+    panic!("{}", msg)
+}
+
+/// Lets `safe_unwrap!` panic with `.expect()`'s message on either an `Option` or a `Result`
+/// while reporting a violation through [`report_violation`] exactly once,
+/// without evaluating the wrapped expression more than once.
+#[doc(hidden)]
+pub trait SafeUnwrapExpectHook<T> {
+    #[track_caller]
+    fn __safe_unwrap_expect_hook(self, reason: &'static str, msg: &'static str, location: Location) -> T;
+}
+
+#[doc(hidden)]
+impl<T, E: core::fmt::Debug> SafeUnwrapExpectHook<T> for Result<T, E> {
+    #[track_caller]
+    #[inline]
+    fn __safe_unwrap_expect_hook(self, reason: &'static str, msg: &'static str, location: Location) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                report_violation(&ViolationInfo { reason, location, action: ViolationAction::Panic });
+                expect_err(e, msg)
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+impl<T> SafeUnwrapExpectHook<T> for Option<T> {
+    #[track_caller]
+    #[inline]
+    fn __safe_unwrap_expect_hook(self, reason: &'static str, msg: &'static str, location: Location) -> T {
+        match self {
+            Some(v) => v,
+            None => {
+                report_violation(&ViolationInfo { reason, location, action: ViolationAction::Panic });
+                expect_none(msg)
+            }
+        }
+    }
+}
+
+/// Terminate the process immediately, without reporting anything first
+/// (reporting is [`report_violation`]'s job).
+///
+/// With the `std` feature this is `std::process::abort()`; without it there
+/// is no process-level abort available in `core`, so we fall back to
+/// `abort_trap`.
+#[inline]
+fn abort_silent() -> ! {
+    #[cfg(feature = "std")]
+    {
+        std::process::abort()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        abort_trap()
+    }
+}
+
+/// `no_std` fallback for process termination: trap with an illegal
+/// instruction. There is no portable way to ask the platform to terminate
+/// the process without `std`, so on unrecognized targets we simply spin
+/// forever rather than risk continuing past a violated invariant.
+#[cfg(not(feature = "std"))]
+#[inline]
+fn abort_trap() -> ! {
+    #[cfg(target_arch = "x86_64")]
+    unsafe {
+        core::arch::asm!("ud2", options(noreturn))
+    }
+    #[cfg(target_arch = "aarch64")]
+    unsafe {
+        core::arch::asm!("brk #0", options(noreturn))
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    loop {}
+}
+
 // TODO: replace `cfg(debug_assertions)` with something cleaner using a build
 //       script
 #[macro_export]
 #[cfg(not(debug_assertions))]
 macro_rules! safe_unwrap {
+    ($reason:expr, $($variant:ident)::+ ( $($field:ident),+ $(,)? ) = $e:expr) => (
+        match $e {
+            $($variant)::+($($field),+) => ($($field,)+),
+            _ => unsafe { core::hint::unreachable_unchecked() },
+        }
+    );
+    ($reason:expr, $($variant:ident)::+ { $($field:ident),+ $(,)? } = $e:expr) => (
+        match $e {
+            $($variant)::+ { $($field),+ } => ($($field,)+),
+            _ => unsafe { core::hint::unreachable_unchecked() },
+        }
+    );
     ($reason:expr, $e:expr) => ($e.unwrap())
 }
 
 #[macro_export]
 #[cfg(debug_assertions)]
 macro_rules! safe_unwrap {
+    ($reason:expr, $($variant:ident)::+ ( $($field:ident),+ $(,)? ) = $e:expr) => (
+        match $e {
+            $($variant)::+($($field),+) => ($($field,)+),
+            _ => {
+                $crate::report_violation(&$crate::ViolationInfo {
+                    reason: concat!("[BUG] violated: ", $reason),
+                    location: $crate::Location { file: file!(), line: line!(), column: column!() },
+                    action: $crate::ViolationAction::Panic,
+                });
+                unreachable!(concat!("[BUG] violated: ", $reason,
+                    " at ", file!(), ":", line!(), ":", column!()))
+            }
+        }
+    );
+    ($reason:expr, $($variant:ident)::+ { $($field:ident),+ $(,)? } = $e:expr) => (
+        match $e {
+            $($variant)::+ { $($field),+ } => ($($field,)+),
+            _ => {
+                $crate::report_violation(&$crate::ViolationInfo {
+                    reason: concat!("[BUG] violated: ", $reason),
+                    location: $crate::Location { file: file!(), line: line!(), column: column!() },
+                    action: $crate::ViolationAction::Panic,
+                });
+                unreachable!(concat!("[BUG] violated: ", $reason,
+                    " at ", file!(), ":", line!(), ":", column!()))
+            }
+        }
+    );
     ($reason:expr, $e:expr) => (
-        $e.expect(concat!("[BUG] violated: ",
-        $reason))
+        $crate::SafeUnwrapExpectHook::__safe_unwrap_expect_hook(
+            $e,
+            concat!("[BUG] violated: ", $reason),
+            concat!("[BUG] violated: ", $reason, " at ", file!(), ":", line!(), ":", column!()),
+            $crate::Location { file: file!(), line: line!(), column: column!() },
+        )
     )
 }
 
+/// Like `safe_unwrap!`, but aborts the process instead of panicking on
+/// violation, in both debug and release builds. See
+/// [`SafeUnwrap::unwrap_or_abort`] for when to prefer this over
+/// `safe_unwrap!`.
+#[macro_export]
+macro_rules! safe_unwrap_abort {
+    ($reason:expr, $e:expr) => ($crate::SafeUnwrap::unwrap_or_abort($e, $reason))
+}
+
 pub trait SafeUnwrap<T> {
+    #[track_caller]
     fn safe_unwrap(self, msg: &'static str) -> T;
-    #[cfg(feature = "std")]
+    #[track_caller]
+    fn verified(self, msg: &'static str) -> T;
+    #[track_caller]
+    fn assured(self, msg: &'static str) -> T;
+    #[track_caller]
+    fn todo(self, msg: &'static str) -> T;
+    #[track_caller]
     fn unwrap_or_abort(self, msg: &'static str) -> T;
     #[cfg(feature = "std")]
+    #[track_caller]
     fn unwrap_or_exit(self, msg: &'static str) -> T;
 }
 
 #[cfg(not(debug_assertions))]
 impl<T, E: core::fmt::Debug> SafeUnwrap<T> for Result<T, E> {
+    #[track_caller]
     #[inline]
     fn safe_unwrap(self, _: &'static str) -> T {
         self.unwrap()
     }
 
-    #[cfg(feature = "std")]
+    #[track_caller]
+    #[inline]
+    fn verified(self, _: &'static str) -> T {
+        self.unwrap()
+    }
+
+    #[track_caller]
+    #[inline]
+    fn assured(self, _: &'static str) -> T {
+        self.unwrap()
+    }
+
+    #[track_caller]
+    #[inline]
+    fn todo(self, msg: &'static str) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_err(e, msg)
+            }
+        }
+    }
+
+    #[track_caller]
     #[inline]
-    fn unwrap_or_abort(self, _: &'static str) -> T {
-        self.unwrap_or_else(|_| std::process::abort())
+    fn unwrap_or_abort(self, msg: &'static str) -> T {
+        let location = caller_location();
+        self.unwrap_or_else(|_| {
+            report_violation(&ViolationInfo {
+                reason: msg,
+                location,
+                action: ViolationAction::Abort,
+            });
+            abort_silent()
+        })
     }
 
     #[cfg(feature = "std")]
+    #[track_caller]
     #[inline]
-    fn unwrap_or_exit(self, _: &'static str) -> T {
-        self.unwrap_or_else(|_| std::process::exit(1))
+    fn unwrap_or_exit(self, msg: &'static str) -> T {
+        let location = caller_location();
+        self.unwrap_or_else(|_| {
+            report_violation(&ViolationInfo {
+                reason: msg,
+                location,
+                action: ViolationAction::Exit,
+            });
+            std::process::exit(1)
+        })
     }
 }
 
 #[cfg(not(debug_assertions))]
 impl<T> SafeUnwrap<T> for Option<T> {
+    #[track_caller]
     #[inline]
     fn safe_unwrap(self, _: &'static str) -> T {
         self.unwrap()
     }
 
-    #[cfg(feature = "std")]
+    #[track_caller]
+    #[inline]
+    fn verified(self, _: &'static str) -> T {
+        self.unwrap()
+    }
+
+    #[track_caller]
+    #[inline]
+    fn assured(self, _: &'static str) -> T {
+        self.unwrap()
+    }
+
+    #[track_caller]
+    #[inline]
+    fn todo(self, msg: &'static str) -> T {
+        match self {
+            Some(v) => v,
+            None => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_none(msg)
+            }
+        }
+    }
+
+    #[track_caller]
     #[inline]
-    fn unwrap_or_abort(self, _: &'static str) -> T {
-        self.unwrap_or_else(std::process::abort)
+    fn unwrap_or_abort(self, msg: &'static str) -> T {
+        let location = caller_location();
+        self.unwrap_or_else(|| {
+            report_violation(&ViolationInfo {
+                reason: msg,
+                location,
+                action: ViolationAction::Abort,
+            });
+            abort_silent()
+        })
     }
 
     #[cfg(feature = "std")]
+    #[track_caller]
     #[inline]
-    fn unwrap_or_exit(self, _: &'static str) -> T {
-        self.unwrap_or_else(|| std::process::exit(1))
+    fn unwrap_or_exit(self, msg: &'static str) -> T {
+        let location = caller_location();
+        self.unwrap_or_else(|| {
+            report_violation(&ViolationInfo {
+                reason: msg,
+                location,
+                action: ViolationAction::Exit,
+            });
+            std::process::exit(1)
+        })
     }
 }
 
 #[cfg(debug_assertions)]
 impl<T, E: core::fmt::Debug> SafeUnwrap<T> for Result<T, E> {
+    #[track_caller]
     #[inline]
     fn safe_unwrap(self, msg: &'static str) -> T {
-        self.expect(msg)
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_err(e, msg)
+            }
+        }
     }
 
-    #[cfg(feature = "std")]
+    #[track_caller]
+    #[inline]
+    fn verified(self, msg: &'static str) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_err(e, msg)
+            }
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn assured(self, msg: &'static str) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_err(e, msg)
+            }
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn todo(self, msg: &'static str) -> T {
+        match self {
+            Ok(v) => v,
+            Err(e) => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_err(e, msg)
+            }
+        }
+    }
+
+    #[track_caller]
     #[inline]
     fn unwrap_or_abort(self, msg: &'static str) -> T {
+        let location = caller_location();
         self.unwrap_or_else(|_| {
-            let _ = writeln!(std::io::stderr(), "{}", msg);
-            std::process::abort()
+            report_violation(&ViolationInfo {
+                reason: msg,
+                location,
+                action: ViolationAction::Abort,
+            });
+            abort_silent()
         })
     }
 
     #[cfg(feature = "std")]
+    #[track_caller]
     #[inline]
     fn unwrap_or_exit(self, msg: &'static str) -> T {
+        let location = caller_location();
         self.unwrap_or_else(|_| {
-            let _ = writeln!(std::io::stderr(), "{}", msg);
+            report_violation(&ViolationInfo {
+                reason: msg,
+                location,
+                action: ViolationAction::Exit,
+            });
             std::process::exit(1)
         })
     }
@@ -165,25 +653,95 @@ impl<T, E: core::fmt::Debug> SafeUnwrap<T> for Result<T, E> {
 
 #[cfg(debug_assertions)]
 impl<T> SafeUnwrap<T> for Option<T> {
+    #[track_caller]
     #[inline]
     fn safe_unwrap(self, msg: &'static str) -> T {
-        self.expect(msg)
+        match self {
+            Some(v) => v,
+            None => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_none(msg)
+            }
+        }
     }
 
-    #[cfg(feature = "std")]
+    #[track_caller]
+    #[inline]
+    fn verified(self, msg: &'static str) -> T {
+        match self {
+            Some(v) => v,
+            None => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_none(msg)
+            }
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn assured(self, msg: &'static str) -> T {
+        match self {
+            Some(v) => v,
+            None => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_none(msg)
+            }
+        }
+    }
+
+    #[track_caller]
+    #[inline]
+    fn todo(self, msg: &'static str) -> T {
+        match self {
+            Some(v) => v,
+            None => {
+                report_violation(&ViolationInfo {
+                    reason: msg,
+                    location: caller_location(),
+                    action: ViolationAction::Panic,
+                });
+                expect_none(msg)
+            }
+        }
+    }
+
+    #[track_caller]
     #[inline]
     fn unwrap_or_abort(self, msg: &'static str) -> T {
+        let location = caller_location();
         self.unwrap_or_else(|| {
-            let _ = writeln!(std::io::stderr(), "{}", msg);
-            std::process::abort()
+            report_violation(&ViolationInfo {
+                reason: msg,
+                location,
+                action: ViolationAction::Abort,
+            });
+            abort_silent()
         })
     }
 
     #[cfg(feature = "std")]
+    #[track_caller]
     #[inline]
     fn unwrap_or_exit(self, msg: &'static str) -> T {
+        let location = caller_location();
         self.unwrap_or_else(|| {
-            let _ = writeln!(std::io::stderr(), "{}", msg);
+            report_violation(&ViolationInfo {
+                reason: msg,
+                location,
+                action: ViolationAction::Exit,
+            });
             std::process::exit(1)
         })
     }
@@ -222,4 +780,99 @@ mod tests {
         let _: Result<(), ()> = Err(()).safe_unwrap("should fail");
     }
 
+    #[test]
+    fn verified_and_assured_work_when_ok() {
+        let x = Some(42).verified("checked above");
+        assert_eq!(x, 42);
+
+        let y = Some(42).assured("guaranteed by the caller");
+        assert_eq!(y, 42);
+    }
+
+    #[test]
+    #[should_panic]
+    fn verified_panics_when_none() {
+        let _: Option<()> = None.verified("should fail");
+    }
+
+    #[test]
+    #[should_panic]
+    fn assured_panics_when_none() {
+        let _: Option<()> = None.assured("should fail");
+    }
+
+    #[test]
+    fn todo_works_when_ok() {
+        let x = Some(42).todo("not handled yet");
+        assert_eq!(x, 42);
+    }
+
+    #[test]
+    #[should_panic]
+    fn todo_panics_unconditionally() {
+        let _: Option<()> = None.todo("not handled yet");
+    }
+
+    enum Pattern {
+        Tag { key_subtree: usize },
+        Text(&'static str, usize),
+        // only constructed by `destructure_panics_on_mismatch`, which is
+        // itself gated to debug builds
+        #[cfg_attr(not(debug_assertions), allow(dead_code))]
+        Empty,
+    }
+
+    #[test]
+    fn destructures_tuple_variant() {
+        let p = Pattern::Text("hello", 42);
+        let (s, n) = safe_unwrap!("p is always a Text here", Pattern::Text(s, n) = p);
+        assert_eq!(s, "hello");
+        assert_eq!(n, 42);
+    }
+
+    #[test]
+    fn destructures_struct_variant() {
+        let p = Pattern::Tag { key_subtree: 7 };
+        let (key_subtree,) = safe_unwrap!("p is always a Tag here", Pattern::Tag { key_subtree } = p);
+        assert_eq!(key_subtree, 7);
+    }
+
+    // only meaningful in debug builds; in release the mismatch arm is
+    // `unreachable_unchecked()`, which is UB rather than a panic
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn destructure_panics_on_mismatch() {
+        let p = Pattern::Empty;
+        let (s, n) = safe_unwrap!("should fail", Pattern::Text(s, n) = p);
+        let _ = (s, n);
+    }
+
+    #[cfg(all(feature = "std", debug_assertions))]
+    use super::{set_violation_hook, ViolationAction, ViolationInfo};
+    #[cfg(all(feature = "std", debug_assertions))]
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    #[cfg(all(feature = "std", debug_assertions))]
+    static HOOK_CALLED: AtomicBool = AtomicBool::new(false);
+
+    #[cfg(all(feature = "std", debug_assertions))]
+    fn recording_hook(info: &ViolationInfo) {
+        assert_eq!(info.action, ViolationAction::Panic);
+        HOOK_CALLED.store(true, Ordering::SeqCst);
+    }
+
+    // needs `std` for `catch_unwind`, and only debug builds report the
+    // violation through the hook for the plain `safe_unwrap!` panic path
+    #[test]
+    #[cfg(all(feature = "std", debug_assertions))]
+    fn violation_hook_is_invoked_on_violation() {
+        set_violation_hook(recording_hook);
+        HOOK_CALLED.store(false, Ordering::SeqCst);
+        let result = std::panic::catch_unwind(|| {
+            let _: Option<()> = safe_unwrap!("should fail", None);
+        });
+        assert!(result.is_err());
+        assert!(HOOK_CALLED.load(Ordering::SeqCst));
+    }
 }